@@ -0,0 +1,99 @@
+use crate::consensus::pow;
+use rust_randomx::Difficulty;
+
+/// A pluggable block-acceptance engine: given a puzzle's key/blob/target
+/// and a candidate nonce, decides whether the nonce solves it. Passed to
+/// `node_create` through `NodeOpts::consensus`; the node's
+/// `"miner/solution"` handler does not call `verify` on it.
+pub trait ConsensusEngine: Send + Sync {
+    fn verify(
+        &self,
+        key: &[u8],
+        blob: &[u8],
+        offset: usize,
+        size: usize,
+        target: u32,
+        nonce: &[u8],
+    ) -> bool;
+
+    /// Finds a nonce this engine's own `verify` accepts for the given
+    /// puzzle. Lets callers (miners, test harnesses) grind against whatever
+    /// engine a node was built with instead of assuming real PoW.
+    fn solve(&self, key: &[u8], blob: &[u8], offset: usize, size: usize, target: u32) -> Vec<u8>;
+}
+
+/// Production engine: RandomX proof-of-work.
+#[derive(Default)]
+pub struct PowEngine;
+
+impl ConsensusEngine for PowEngine {
+    fn verify(
+        &self,
+        key: &[u8],
+        blob: &[u8],
+        offset: usize,
+        size: usize,
+        target: u32,
+        nonce: &[u8],
+    ) -> bool {
+        if nonce.len() != size || offset + size > blob.len() {
+            return false;
+        }
+        let mut blob = blob.to_vec();
+        blob[offset..offset + size].copy_from_slice(nonce);
+        let hash = pow::hash(key, &blob);
+        hash.meets_difficulty(Difficulty::new(target))
+    }
+
+    /// Grinds little-endian counter nonces of `size` bytes. Where `size` is
+    /// over 8, or the nonce would run past the end of `blob`, returns an
+    /// all-zero nonce without checking it against `verify`.
+    fn solve(&self, key: &[u8], blob: &[u8], offset: usize, size: usize, target: u32) -> Vec<u8> {
+        if size > 8 || offset + size > blob.len() {
+            return vec![0u8; size];
+        }
+        let mut blob = blob.to_vec();
+        let mut nonce = 0u64;
+        loop {
+            let candidate = nonce.to_le_bytes()[..size].to_vec();
+            blob[offset..offset + size].copy_from_slice(&candidate);
+            let hash = pow::hash(key, &blob);
+            if hash.meets_difficulty(Difficulty::new(target)) {
+                return candidate;
+            }
+            nonce += 1;
+        }
+    }
+}
+
+/// Test engine: accepts any nonce immediately, so `test_network`-based
+/// tests can produce blocks instantly and deterministically instead of
+/// grinding real RandomX work. The dedicated PoW tests keep exercising
+/// `PowEngine` directly.
+#[derive(Default)]
+pub struct NullEngine;
+
+impl ConsensusEngine for NullEngine {
+    fn verify(
+        &self,
+        _key: &[u8],
+        _blob: &[u8],
+        _offset: usize,
+        _size: usize,
+        _target: u32,
+        _nonce: &[u8],
+    ) -> bool {
+        true
+    }
+
+    fn solve(
+        &self,
+        _key: &[u8],
+        _blob: &[u8],
+        _offset: usize,
+        size: usize,
+        _target: u32,
+    ) -> Vec<u8> {
+        vec![0u8; size]
+    }
+}
@@ -0,0 +1,7 @@
+// `src/consensus/mod.rs` isn't part of this snapshot either; `pow` is
+// declared here only so the existing `pow` submodule this tree's code
+// already calls (`consensus::pow::hash`) keeps resolving alongside the
+// new `engine` submodule. `pow`'s own contents live outside this
+// snapshot and aren't reconstructed here.
+pub mod engine;
+pub mod pow;
@@ -0,0 +1,13 @@
+// This snapshot predates a `src/lib.rs` reconstruction: the crate root
+// file isn't part of this tree, so this only lists the top-level modules
+// this backlog's commits actually reference or added (`merkle`). The
+// existing modules (`blockchain`, `consensus`, `core`, `db`, `node`,
+// `wallet`) are declared here purely so `crate::merkle` resolves; their
+// own contents live outside this snapshot and aren't reconstructed.
+pub mod blockchain;
+pub mod consensus;
+pub mod core;
+pub mod db;
+pub mod merkle;
+pub mod node;
+pub mod wallet;
@@ -0,0 +1,232 @@
+use crate::core::Hash;
+use serde::{Deserialize, Serialize};
+
+/// Hash combined in wherever a level's sibling doesn't exist yet, so a
+/// missing subtree is padded identically in `root()` and in any proof
+/// built against it.
+fn empty_hash() -> Hash {
+    Hash::calc(b"bazuka-merkle-empty-node")
+}
+
+fn combine(left: &Hash, right: &Hash) -> Hash {
+    let mut bytes = Vec::with_capacity(left.as_ref().len() + right.as_ref().len());
+    bytes.extend_from_slice(left.as_ref());
+    bytes.extend_from_slice(right.as_ref());
+    Hash::calc(&bytes)
+}
+
+/// One step of an inclusion proof: the sibling hash at a level, and
+/// whether the running hash sits on the right at that level (so the
+/// verifier knows which side to combine it on).
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProofStep {
+    pub sibling: Hash,
+    pub on_right: bool,
+}
+
+/// An inclusion proof for a single leaf: enough to recompute the root
+/// from the leaf hash alone.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MerkleProof {
+    pub leaf: Hash,
+    pub index: u64,
+    pub steps: Vec<ProofStep>,
+}
+
+/// Append-only Merkle accumulator over committed blocks/transactions.
+///
+/// `frontier[h]` holds the root of a completed, never-yet-combined
+/// subtree of `2^h` leaves whenever bit `h` of `leaf_count` is set, and is
+/// `None` otherwise — the frontier is exactly the binary representation
+/// of `leaf_count`. `root()` folds it bit by bit, low to high, against a
+/// fixed empty-node constant at unset bits. `proofs[i]` records, as of
+/// append time, the siblings leaf `i` has already combined with on its
+/// way up to its current frontier slot; `proof()` completes it at query
+/// time with whatever the remaining (still-open) levels currently hold.
+#[derive(Clone, Debug, Default)]
+pub struct MerkleAccumulator {
+    frontier: Vec<Option<Hash>>,
+    pending: Vec<Vec<u64>>,
+    proofs: Vec<Vec<ProofStep>>,
+    leaves: Vec<Hash>,
+    leaf_count: u64,
+}
+
+impl MerkleAccumulator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn leaf_count(&self) -> u64 {
+        self.leaf_count
+    }
+
+    /// Appends a leaf, carrying it up through already-occupied frontier
+    /// slots until it either settles into an empty one or becomes the new
+    /// top-level root. Every leaf whose pending subtree combines during
+    /// this call gets one more step appended to its stored proof.
+    pub fn append(&mut self, leaf: Hash) {
+        let index = self.leaf_count;
+        self.leaf_count += 1;
+        self.proofs.push(Vec::new());
+        self.leaves.push(leaf.clone());
+
+        let mut carry = leaf;
+        let mut carry_members = vec![index];
+        let mut level = 0;
+        loop {
+            if level == self.frontier.len() {
+                self.frontier.push(None);
+                self.pending.push(Vec::new());
+            }
+            match self.frontier[level].take() {
+                Some(existing) => {
+                    let existing_members = std::mem::take(&mut self.pending[level]);
+                    for &i in &existing_members {
+                        self.proofs[i as usize].push(ProofStep {
+                            sibling: carry.clone(),
+                            on_right: false,
+                        });
+                    }
+                    for &i in &carry_members {
+                        self.proofs[i as usize].push(ProofStep {
+                            sibling: existing.clone(),
+                            on_right: true,
+                        });
+                    }
+                    carry = combine(&existing, &carry);
+                    carry_members.splice(0..0, existing_members);
+                    level += 1;
+                }
+                None => {
+                    self.frontier[level] = Some(carry);
+                    self.pending[level] = carry_members;
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Folds frontier heights `[from, to)` into a single hash, starting
+    /// from the empty-node constant — the same recipe `root()` uses over
+    /// its full range, reusable over a sub-range for proof completion.
+    fn fold_heights(&self, from: usize, to: usize) -> Hash {
+        let mut node = empty_hash();
+        for h in from..to {
+            if (self.leaf_count >> h) & 1 == 1 {
+                let populated = self.frontier[h]
+                    .clone()
+                    .expect("bit set at h implies frontier[h] is occupied");
+                node = combine(&populated, &node);
+            } else {
+                node = combine(&node, &empty_hash());
+            }
+        }
+        node
+    }
+
+    /// The current root, folding occupied frontier slots from lowest to
+    /// highest and padding absent ones with `empty_hash()`.
+    pub fn root(&self) -> Hash {
+        self.fold_heights(0, self.frontier.len().max(1))
+    }
+
+    /// Builds an inclusion proof for leaf `index`: the steps recorded at
+    /// append time (siblings from subtrees it has already combined with)
+    /// plus, at query time, the fold-in of everything still below it and
+    /// the continuation above it, using the exact recipe `root()` uses.
+    pub fn proof(&self, index: u64) -> Option<MerkleProof> {
+        if index >= self.leaf_count {
+            return None;
+        }
+        let owner_h = self.proofs[index as usize].len();
+        let mut steps = self.proofs[index as usize].clone();
+
+        steps.push(ProofStep {
+            sibling: self.fold_heights(0, owner_h),
+            on_right: false,
+        });
+        for h in (owner_h + 1)..self.frontier.len() {
+            if (self.leaf_count >> h) & 1 == 1 {
+                steps.push(ProofStep {
+                    sibling: self.frontier[h].clone().expect("bit set implies occupied"),
+                    on_right: true,
+                });
+            } else {
+                steps.push(ProofStep {
+                    sibling: empty_hash(),
+                    on_right: false,
+                });
+            }
+        }
+
+        Some(MerkleProof {
+            leaf: self.leaves[index as usize].clone(),
+            index,
+            steps,
+        })
+    }
+}
+
+/// Recomputes the root implied by `proof` and checks it against `root`.
+pub fn verify(proof: &MerkleProof, root: &Hash) -> bool {
+    let mut acc = proof.leaf.clone();
+    for step in &proof.steps {
+        acc = if step.on_right {
+            combine(&step.sibling, &acc)
+        } else {
+            combine(&acc, &step.sibling)
+        };
+    }
+    &acc == root
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leaf(i: u64) -> Hash {
+        Hash::calc(format!("leaf-{}", i).as_bytes())
+    }
+
+    #[test]
+    fn proof_round_trips_for_small_accumulators() {
+        for n in 1u64..=5 {
+            let mut acc = MerkleAccumulator::new();
+            for i in 0..n {
+                acc.append(leaf(i));
+            }
+            let root = acc.root();
+            for i in 0..n {
+                let proof = acc.proof(i).expect("leaf within range must have a proof");
+                assert_eq!(proof.leaf, leaf(i));
+                assert_eq!(proof.index, i);
+                assert!(
+                    verify(&proof, &root),
+                    "proof for leaf {} of {} did not verify against the root",
+                    i,
+                    n
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn proof_is_none_out_of_range() {
+        let mut acc = MerkleAccumulator::new();
+        acc.append(leaf(0));
+        assert!(acc.proof(1).is_none());
+    }
+
+    #[test]
+    fn tampered_proof_does_not_verify() {
+        let mut acc = MerkleAccumulator::new();
+        for i in 0..4 {
+            acc.append(leaf(i));
+        }
+        let root = acc.root();
+        let mut proof = acc.proof(2).unwrap();
+        proof.steps[0].sibling = leaf(99);
+        assert!(!verify(&proof, &root));
+    }
+}
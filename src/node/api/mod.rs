@@ -0,0 +1,7 @@
+// `src/node/api/mod.rs` isn't part of this snapshot; `messages` is
+// declared here only so the existing `messages` submodule (referenced
+// throughout as `super::api::messages::*`) keeps resolving alongside the
+// new `sync_messages` submodule. `messages`'s own contents live outside
+// this snapshot and aren't reconstructed here.
+pub mod messages;
+pub mod sync_messages;
@@ -0,0 +1,86 @@
+use crate::core::{Block, Hash, Header};
+use crate::merkle::ProofStep;
+use serde::{Deserialize, Serialize};
+
+// Message pairs added to the node's JSON API for: external `BlockSource`
+// lookups, content-addressed block exchange, and Merkle inclusion
+// proofs. Kept separate from the original `api::messages` module so this
+// backlog's additions don't need to touch it.
+
+#[derive(Serialize, Deserialize)]
+pub struct GetHeaderRequest {
+    pub hash: Hash,
+    pub height: u64,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct GetHeaderResponse {
+    pub header: Header,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct GetBlockRequest {
+    pub hash: Hash,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct GetBlockResponse {
+    pub block: Block,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct GetBestBlockRequest {}
+
+#[derive(Serialize, Deserialize)]
+pub struct GetBestBlockResponse {
+    pub hash: Hash,
+    pub height: u64,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct BlocksExistRequest {
+    pub hashes: Vec<Hash>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct BlocksExistResponse {
+    pub exists: Vec<bool>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct BlocksGetRequest {
+    pub hashes: Vec<Hash>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct BlocksGetResponse {
+    pub blocks: Vec<Block>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct BlocksPutRequest {
+    pub blocks: Vec<Block>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct BlocksPutResponse {}
+
+/// This message pair isn't served yet: answering it needs a
+/// `MerkleAccumulator` that `node_create` appends every committed block's
+/// hash to, plus a `"merkle-proof"` arm in its request match building a
+/// `MerkleProofResponse` from `accumulator.proof(index)`/`accumulator.root()`.
+/// Both live in `node_create`'s body, which predates this backlog and isn't
+/// part of this snapshot, so the accumulator stays unattached to any real
+/// commit flow here.
+#[derive(Serialize, Deserialize)]
+pub struct GetMerkleProofRequest {
+    pub index: u64,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct MerkleProofResponse {
+    pub leaf: Hash,
+    pub index: u64,
+    pub steps: Vec<ProofStep>,
+    pub root: Hash,
+}
@@ -0,0 +1,188 @@
+use crate::blockchain::{Blockchain, KvStoreChain};
+use crate::core::{Block, Hash, Header};
+use crate::db::KvStore;
+use crate::node::api::sync_messages::*;
+use crate::node::NodeError;
+
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::RwLock;
+
+/// Whatever `PeerBlockSource` needs from a peer connection: issuing a
+/// JSON GET in the node's existing wire format. Implemented, for tests,
+/// by `node::test::simulation::SenderWrapper`.
+#[async_trait::async_trait]
+pub trait JsonPeer: Send + Sync {
+    async fn json_get<Req, Resp>(&self, url: &str, req: Req) -> Result<Resp, NodeError>
+    where
+        Req: serde::Serialize + Send + Sync + 'static,
+        Resp: serde::de::DeserializeOwned;
+}
+
+/// A read-only source of chain data external to peer gossip, such as a
+/// trusted REST/RPC endpoint a node can poll to bootstrap or cross-check
+/// its tip. Methods take `&self` so a single source can be shared across
+/// several concurrent sync tasks.
+#[async_trait::async_trait]
+pub trait BlockSource: Send + Sync {
+    async fn get_header(&self, hash: Hash, height: u64) -> Result<Header, NodeError>;
+    async fn get_block(&self, hash: Hash) -> Result<Block, NodeError>;
+    async fn get_best_block(&self) -> Result<(Hash, u64), NodeError>;
+}
+
+/// `BlockSource` backed by an existing peer connection, reusing the node's
+/// own JSON wire format.
+pub struct PeerBlockSource<P: JsonPeer> {
+    peer: P,
+}
+
+impl<P: JsonPeer> PeerBlockSource<P> {
+    pub fn new(peer: P) -> Self {
+        Self { peer }
+    }
+}
+
+#[async_trait::async_trait]
+impl<P: JsonPeer> BlockSource for PeerBlockSource<P> {
+    async fn get_header(&self, hash: Hash, height: u64) -> Result<Header, NodeError> {
+        self.peer
+            .json_get::<GetHeaderRequest, GetHeaderResponse>(
+                "header",
+                GetHeaderRequest { hash, height },
+            )
+            .await
+            .map(|resp| resp.header)
+    }
+    async fn get_block(&self, hash: Hash) -> Result<Block, NodeError> {
+        self.peer
+            .json_get::<GetBlockRequest, GetBlockResponse>("block", GetBlockRequest { hash })
+            .await
+            .map(|resp| resp.block)
+    }
+    async fn get_best_block(&self) -> Result<(Hash, u64), NodeError> {
+        self.peer
+            .json_get::<GetBestBlockRequest, GetBestBlockResponse>(
+                "best-block",
+                GetBestBlockRequest {},
+            )
+            .await
+            .map(|resp| (resp.hash, resp.height))
+    }
+}
+
+/// `BlockSource` backed by a plain HTTP REST endpoint, for bootstrapping or
+/// cross-checking against a trusted server that isn't itself a gossip peer.
+pub struct HttpBlockSource {
+    client: hyper::Client<hyper::client::HttpConnector>,
+    base_url: String,
+}
+
+impl HttpBlockSource {
+    pub fn new(base_url: String) -> Self {
+        Self {
+            client: hyper::Client::new(),
+            base_url,
+        }
+    }
+
+    async fn get_json<Resp: serde::de::DeserializeOwned>(
+        &self,
+        path: &str,
+    ) -> Result<Resp, NodeError> {
+        let uri: hyper::Uri = format!("{}/{}", self.base_url, path)
+            .parse()
+            .map_err(|_| NodeError::NotAnsweringError)?;
+        let resp = self
+            .client
+            .get(uri)
+            .await
+            .map_err(|_| NodeError::NotAnsweringError)?;
+        let body = hyper::body::to_bytes(resp.into_body()).await?;
+        Ok(serde_json::from_slice(&body)?)
+    }
+}
+
+#[async_trait::async_trait]
+impl BlockSource for HttpBlockSource {
+    async fn get_header(&self, hash: Hash, height: u64) -> Result<Header, NodeError> {
+        self.get_json(&format!("header/{}/{}", hash, height)).await
+    }
+    async fn get_block(&self, hash: Hash) -> Result<Block, NodeError> {
+        self.get_json(&format!("block/{}", hash)).await
+    }
+    async fn get_best_block(&self) -> Result<(Hash, u64), NodeError> {
+        let resp: GetBestBlockResponse = self.get_json("best-block").await?;
+        Ok((resp.hash, resp.height))
+    }
+}
+
+/// Repeatedly asks one or more `BlockSource`s for their current best block
+/// and feeds any newly-announced headers/blocks into the local chain.
+/// Since `BlockSource` only needs `&self`, several `PollingSyncClient`s (or
+/// tasks sharing one) can poll concurrently without coordination.
+pub struct PollingSyncClient<K: KvStore> {
+    sources: Vec<Arc<dyn BlockSource>>,
+    chain: Arc<RwLock<KvStoreChain<K>>>,
+    interval: Duration,
+}
+
+impl<K: KvStore + Send + Sync + 'static> PollingSyncClient<K> {
+    pub fn new(
+        sources: Vec<Arc<dyn BlockSource>>,
+        chain: Arc<RwLock<KvStoreChain<K>>>,
+        interval: Duration,
+    ) -> Self {
+        Self {
+            sources,
+            chain,
+            interval,
+        }
+    }
+
+    /// Runs the polling loop forever. A source failing to answer just
+    /// skips this tick; it's retried on the next one.
+    pub async fn run(&self) -> Result<(), NodeError> {
+        loop {
+            for source in &self.sources {
+                let _ = self.sync_once(source.as_ref()).await;
+            }
+            tokio::time::sleep(self.interval).await;
+        }
+    }
+
+    /// Walks back from the announced tip via `parent_hash` to collect the
+    /// missing range, then applies it oldest-first in a single `extend`
+    /// call so the chain's parent-linkage check never sees a gap.
+    async fn sync_once(&self, source: &dyn BlockSource) -> Result<(), NodeError> {
+        let (best_hash, best_height) = source.get_best_block().await?;
+        let our_height = self.chain.read().await.get_height()?;
+        if best_height <= our_height {
+            return Ok(());
+        }
+        let mut pending = Vec::new();
+        let mut cursor_hash = best_hash;
+        for height in (our_height + 1..=best_height).rev() {
+            let header = source.get_header(cursor_hash.clone(), height).await?;
+            let block = source.get_block(cursor_hash).await?;
+            pending.push(block);
+            cursor_hash = header.parent_hash;
+        }
+        pending.reverse();
+        self.chain.write().await.extend(our_height, &pending)?;
+        Ok(())
+    }
+}
+
+// `BlockSource`/`PollingSyncClient` aren't exercised by a test here: doing
+// so needs a constructible `Block`/`Header` pair and a running chain,
+// neither of which this tree has outside `node::test` (this module is
+// intentionally not part of that test-only subtree, per the request that
+// these be usable by a real node, not just the test harness).
+//
+// `PeerBlockSource` is also unreachable end to end: the `"header"`,
+// `"block"`, and `"best-block"` routes it calls via `JsonPeer::json_get`
+// need matching arms in `node_create`'s request match answering from the
+// local chain, the same way `"stats"`/`"peers"` already do. That match
+// lives in `node/mod.rs`, which predates this backlog and isn't part of
+// this snapshot, so those arms — and a `test_network` case exercising a
+// peer-backed sync — can't be added here.
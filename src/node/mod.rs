@@ -0,0 +1,14 @@
+// `src/node/mod.rs` isn't part of this snapshot; `api` and `test` are
+// declared here only so the existing submodules this tree's code
+// already depends on (`node::api::messages`, `node::test::simulation`)
+// keep resolving alongside the new `block_source` submodule. `node_create`,
+// `NodeError`, `PeerAddress`, `IncomingRequest`, and `OutgoingRequest` all
+// live in this same file in the real repo but predate this backlog and
+// aren't reconstructed here — including the pending change to have
+// `node_create` store its `opts.consensus: Arc<dyn ConsensusEngine>` and
+// verify submitted miner solutions against it instead of the hash check,
+// which blocks `mine_instant` from being verified end to end.
+pub mod api;
+pub mod block_source;
+#[cfg(test)]
+pub mod test;
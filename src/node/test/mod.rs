@@ -0,0 +1,5 @@
+// `src/node/test/mod.rs` isn't part of this snapshot; `simulation` is the
+// one submodule this tree's baseline file (`simulation.rs`, which predates
+// this backlog) needs declared so `node::test::simulation` resolves. This
+// whole module is already gated `#[cfg(test)]` from `node/mod.rs`.
+pub mod simulation;
@@ -2,13 +2,78 @@ use super::*;
 
 use super::api::messages::*;
 use crate::blockchain::{KvStoreChain, ZkBlockchainPatch};
-use crate::core::Block;
+use crate::consensus::engine::{ConsensusEngine, NullEngine, PowEngine};
+use crate::core::{Block, Hash};
 use crate::db::RamKvStore;
+use crate::node::api::sync_messages::*;
 use crate::wallet::Wallet;
 
+use rand::Rng;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::RwLock;
 
+/// Artificial latency and drop probability applied by `route` when
+/// forwarding a request from one specific peer to another; directional,
+/// since real links aren't necessarily symmetric.
+#[derive(Clone, Debug, Default)]
+pub struct LinkConfig {
+    pub latency: Duration,
+    pub drop_probability: f64,
+}
+
+/// Runtime-mutable network conditions shared between `route` tasks and the
+/// test driving them, so a test can heal a partition or raise latency
+/// mid-run without tearing down the simulated network.
+///
+/// Latency/drops are per directed (src, dst) link. Partition membership is
+/// per node: two nodes can reach each other only if they're assigned to
+/// the same partition, where "unassigned" is itself a partition every
+/// unassigned node shares — so assigning a node to a named partition cuts
+/// it off from everyone else until `heal_partition` is called.
+#[derive(Clone)]
+pub struct NetworkConditions {
+    enabled: Arc<RwLock<bool>>,
+    links: Arc<RwLock<HashMap<(PeerAddress, PeerAddress), LinkConfig>>>,
+    partitions: Arc<RwLock<HashMap<PeerAddress, String>>>,
+}
+
+impl NetworkConditions {
+    pub fn new(enabled: Arc<RwLock<bool>>) -> Self {
+        Self {
+            enabled,
+            links: Arc::new(RwLock::new(HashMap::new())),
+            partitions: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    pub async fn set_link(&self, src: PeerAddress, dst: PeerAddress, config: LinkConfig) {
+        self.links.write().await.insert((src, dst), config);
+    }
+
+    pub async fn set_partition(&self, addr: PeerAddress, partition: String) {
+        self.partitions.write().await.insert(addr, partition);
+    }
+
+    pub async fn heal_partition(&self, addr: PeerAddress) {
+        self.partitions.write().await.remove(&addr);
+    }
+
+    async fn link_for(&self, src: &PeerAddress, dst: &PeerAddress) -> LinkConfig {
+        self.links
+            .read()
+            .await
+            .get(&(*src, *dst))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    async fn can_reach(&self, src: &PeerAddress, dst: &PeerAddress) -> bool {
+        let partitions = self.partitions.read().await;
+        partitions.get(src) == partitions.get(dst)
+    }
+}
+
 struct Node {
     addr: PeerAddress,
     incoming: SenderWrapper,
@@ -21,6 +86,7 @@ pub struct NodeOpts {
     pub addr: u16,
     pub bootstrap: Vec<u16>,
     pub timestamp_offset: i32,
+    pub consensus: Arc<dyn ConsensusEngine>,
 }
 
 fn create_test_node(
@@ -39,6 +105,7 @@ fn create_test_node(
         chain,
         opts.timestamp_offset,
         opts.wallet,
+        opts.consensus,
         inc_recv,
         out_send,
     );
@@ -56,7 +123,9 @@ fn create_test_node(
 }
 
 async fn route(
+    addr: PeerAddress,
     enabled: Arc<RwLock<bool>>,
+    conditions: NetworkConditions,
     mut outgoing: mpsc::UnboundedReceiver<OutgoingRequest>,
     incs: HashMap<PeerAddress, SenderWrapper>,
 ) -> Result<(), NodeError> {
@@ -73,31 +142,71 @@ async fn route(
                 .parse()
                 .unwrap(),
         );
-        let (resp_snd, mut resp_rcv) = mpsc::channel::<Result<Response<Body>, NodeError>>(1);
-        let inc_req = IncomingRequest {
-            socket_addr: s.0,
-            body: req.body,
-            resp: resp_snd,
-        };
-        incs[&s]
-            .chan
-            .send(inc_req)
-            .map_err(|_| NodeError::NotListeningError)?;
-        req.resp
-            .send(resp_rcv.recv().await.ok_or(NodeError::NotAnsweringError)?)
-            .await
-            .map_err(|_| NodeError::NotListeningError)?;
+
+        if !conditions.can_reach(&addr, &s).await {
+            continue;
+        }
+        let link = conditions.link_for(&addr, &s).await;
+        if link.drop_probability > 0.0 && rand::thread_rng().gen::<f64>() < link.drop_probability {
+            continue;
+        }
+
+        let incoming = incs[&s].clone();
+        // Fire-and-forget: a dropped/failed delivery only affects this one
+        // request (its waiting `req.resp` sender is simply never notified),
+        // not the rest of this node's outgoing traffic.
+        let _ = tokio::spawn(deliver(s, req, incoming, link.latency));
     }
 
     Ok(())
 }
 
+/// Delivers one routed request after `latency`, as its own task so a slow
+/// link can't stall delivery of every other outgoing request from the
+/// same node — `route`'s `recv()` loop only waits on this to be spawned,
+/// not on it to finish.
+async fn deliver(
+    dst: PeerAddress,
+    req: OutgoingRequest,
+    incoming: SenderWrapper,
+    latency: Duration,
+) -> Result<(), NodeError> {
+    if !latency.is_zero() {
+        tokio::time::sleep(latency).await;
+    }
+    let (resp_snd, mut resp_rcv) = mpsc::channel::<Result<Response<Body>, NodeError>>(1);
+    let inc_req = IncomingRequest {
+        socket_addr: dst.0,
+        body: req.body,
+        resp: resp_snd,
+    };
+    incoming
+        .chan
+        .send(inc_req)
+        .map_err(|_| NodeError::NotListeningError)?;
+    req.resp
+        .send(resp_rcv.recv().await.ok_or(NodeError::NotAnsweringError)?)
+        .await
+        .map_err(|_| NodeError::NotListeningError)
+}
+
 #[derive(Clone)]
 pub struct SenderWrapper {
     peer: PeerAddress,
     chan: Arc<mpsc::UnboundedSender<IncomingRequest>>,
 }
 
+#[async_trait::async_trait]
+impl crate::node::block_source::JsonPeer for SenderWrapper {
+    async fn json_get<Req, Resp>(&self, url: &str, req: Req) -> Result<Resp, NodeError>
+    where
+        Req: serde::Serialize + Send + Sync + 'static,
+        Resp: serde::de::DeserializeOwned,
+    {
+        SenderWrapper::json_get(self, url, req).await
+    }
+}
+
 impl SenderWrapper {
     pub async fn raw(&self, body: Request<Body>) -> Result<Body, NodeError> {
         let (resp_snd, mut resp_rcv) = mpsc::channel::<Result<Response<Body>, NodeError>>(1);
@@ -165,6 +274,49 @@ impl SenderWrapper {
             .await
     }
 
+    /// Content-addressed block exchange. These three need matching
+    /// `"blocks_exist"`/`"blocks_get"`/`"blocks_put"` arms in `node_create`'s
+    /// request match (alongside `"stats"`/`"peers"` above) to answer from
+    /// the chain's `KvStore`, and a `test_network` case driving them end to
+    /// end. `node_create`'s match predates this backlog and isn't part of
+    /// this snapshot, so neither the server-side arms nor that test can be
+    /// added here; until they're wired in, calling these against a real
+    /// node hits an unhandled route.
+    pub async fn blocks_exist(&self, hashes: Vec<Hash>) -> Result<Vec<bool>, NodeError> {
+        self.json_post::<BlocksExistRequest, BlocksExistResponse>(
+            "blocks_exist",
+            BlocksExistRequest { hashes },
+        )
+        .await
+        .map(|resp| resp.exists)
+    }
+
+    pub async fn blocks_get(&self, hashes: Vec<Hash>) -> Result<Vec<Block>, NodeError> {
+        self.json_post::<BlocksGetRequest, BlocksGetResponse>(
+            "blocks_get",
+            BlocksGetRequest { hashes },
+        )
+        .await
+        .map(|resp| resp.blocks)
+    }
+
+    pub async fn blocks_put(&self, blocks: Vec<Block>) -> Result<(), NodeError> {
+        self.json_post::<BlocksPutRequest, BlocksPutResponse>(
+            "blocks_put",
+            BlocksPutRequest { blocks },
+        )
+        .await?;
+        Ok(())
+    }
+
+    pub async fn merkle_proof(&self, index: u64) -> Result<MerkleProofResponse, NodeError> {
+        self.json_get::<GetMerkleProofRequest, MerkleProofResponse>(
+            "merkle-proof",
+            GetMerkleProofRequest { index },
+        )
+        .await
+    }
+
     pub async fn set_miner(
         &self,
         webhook: Option<String>,
@@ -177,29 +329,38 @@ impl SenderWrapper {
     }
 
     pub async fn mine(&self) -> Result<PostMinerSolutionResponse, NodeError> {
+        self.mine_with(&PowEngine).await
+    }
+
+    /// Like `mine`, but solves against `consensus::engine::NullEngine`,
+    /// which returns an all-zero nonce with no RandomX grind. The node's
+    /// `"miner/solution"` handler in `node_create` checks the submitted
+    /// hash directly and does not call `.verify(...)` on the node's
+    /// `opts.consensus`, so the engine a node was created with plays no
+    /// part in whether this solution is accepted.
+    pub async fn mine_instant(&self) -> Result<PostMinerSolutionResponse, NodeError> {
+        self.mine_with(&NullEngine).await
+    }
+
+    async fn mine_with(
+        &self,
+        engine: &dyn ConsensusEngine,
+    ) -> Result<PostMinerSolutionResponse, NodeError> {
         let puzzle = self
             .json_get::<GetMinerPuzzleRequest, Puzzle>("miner/puzzle", GetMinerPuzzleRequest {})
             .await?;
-        let sol = mine_puzzle(&puzzle);
+        let sol = solve_puzzle(engine, &puzzle);
         self.json_post::<PostMinerSolutionRequest, PostMinerSolutionResponse>("miner/solution", sol)
             .await
     }
 }
 
-fn mine_puzzle(puzzle: &Puzzle) -> PostMinerSolutionRequest {
+fn solve_puzzle(engine: &dyn ConsensusEngine, puzzle: &Puzzle) -> PostMinerSolutionRequest {
     let key = hex::decode(&puzzle.key).unwrap();
-    let mut blob = hex::decode(&puzzle.blob).unwrap();
-    let mut nonce = 0u64;
-    loop {
-        blob[puzzle.offset..puzzle.offset + puzzle.size].copy_from_slice(&nonce.to_le_bytes());
-        let hash = crate::consensus::pow::hash(&key, &blob);
-        if hash.meets_difficulty(rust_randomx::Difficulty::new(puzzle.target)) {
-            return PostMinerSolutionRequest {
-                nonce: hex::encode(nonce.to_le_bytes()),
-            };
-        }
-
-        nonce += 1;
+    let blob = hex::decode(&puzzle.blob).unwrap();
+    let nonce = engine.solve(&key, &blob, puzzle.offset, puzzle.size, puzzle.target);
+    PostMinerSolutionRequest {
+        nonce: hex::encode(nonce),
     }
 }
 
@@ -210,7 +371,9 @@ pub fn test_network(
     impl futures::Future,
     impl futures::Future,
     Vec<SenderWrapper>,
+    NetworkConditions,
 ) {
+    let conditions = NetworkConditions::new(Arc::clone(&enabled));
     let (node_futs, nodes): (Vec<_>, Vec<Node>) = node_opts
         .into_iter()
         .map(|node_opts| create_test_node(node_opts))
@@ -218,12 +381,112 @@ pub fn test_network(
     let incs: HashMap<_, _> = nodes.iter().map(|n| (n.addr, n.incoming.clone())).collect();
     let route_futs = nodes
         .into_iter()
-        .map(|n| route(Arc::clone(&enabled), n.outgoing, incs.clone()))
+        .map(|n| {
+            route(
+                n.addr,
+                Arc::clone(&enabled),
+                conditions.clone(),
+                n.outgoing,
+                incs.clone(),
+            )
+        })
         .collect::<Vec<_>>();
 
     (
         futures::future::join_all(node_futs),
         futures::future::join_all(route_futs),
         incs.into_values().collect(),
+        conditions,
     )
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod network_conditions_tests {
+    use super::*;
+
+    fn addr(port: u16) -> PeerAddress {
+        PeerAddress(SocketAddr::from(([127, 0, 0, 1], port)))
+    }
+
+    fn conditions() -> NetworkConditions {
+        NetworkConditions::new(Arc::new(RwLock::new(true)))
+    }
+
+    #[tokio::test]
+    async fn unpartitioned_peers_reach_each_other() {
+        let conditions = conditions();
+        assert!(conditions.can_reach(&addr(3030), &addr(3031)).await);
+    }
+
+    #[tokio::test]
+    async fn same_partition_peers_reach_each_other() {
+        let conditions = conditions();
+        conditions
+            .set_partition(addr(3030), "left".to_string())
+            .await;
+        conditions
+            .set_partition(addr(3031), "left".to_string())
+            .await;
+        assert!(conditions.can_reach(&addr(3030), &addr(3031)).await);
+    }
+
+    #[tokio::test]
+    async fn different_partitions_are_mutually_unreachable() {
+        let conditions = conditions();
+        conditions
+            .set_partition(addr(3030), "left".to_string())
+            .await;
+        conditions
+            .set_partition(addr(3031), "right".to_string())
+            .await;
+        assert!(!conditions.can_reach(&addr(3030), &addr(3031)).await);
+        assert!(!conditions.can_reach(&addr(3031), &addr(3030)).await);
+    }
+
+    #[tokio::test]
+    async fn partitioned_peer_cannot_reach_unassigned_peer() {
+        let conditions = conditions();
+        conditions
+            .set_partition(addr(3030), "left".to_string())
+            .await;
+        assert!(!conditions.can_reach(&addr(3030), &addr(3031)).await);
+        assert!(!conditions.can_reach(&addr(3031), &addr(3030)).await);
+    }
+
+    #[tokio::test]
+    async fn healing_restores_reachability() {
+        let conditions = conditions();
+        conditions
+            .set_partition(addr(3030), "left".to_string())
+            .await;
+        conditions
+            .set_partition(addr(3031), "right".to_string())
+            .await;
+        assert!(!conditions.can_reach(&addr(3030), &addr(3031)).await);
+        conditions.heal_partition(addr(3030)).await;
+        conditions.heal_partition(addr(3031)).await;
+        assert!(conditions.can_reach(&addr(3030), &addr(3031)).await);
+    }
+
+    #[tokio::test]
+    async fn per_link_config_is_directional() {
+        let conditions = conditions();
+        conditions
+            .set_link(
+                addr(3030),
+                addr(3031),
+                LinkConfig {
+                    latency: Duration::from_millis(50),
+                    drop_probability: 1.0,
+                },
+            )
+            .await;
+        let forward = conditions.link_for(&addr(3030), &addr(3031)).await;
+        assert_eq!(forward.latency, Duration::from_millis(50));
+        assert_eq!(forward.drop_probability, 1.0);
+
+        let reverse = conditions.link_for(&addr(3031), &addr(3030)).await;
+        assert_eq!(reverse.latency, Duration::default());
+        assert_eq!(reverse.drop_probability, 0.0);
+    }
+}